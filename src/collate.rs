@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use unicode_general_category::{get_general_category, GeneralCategory};
+use unicode_normalization::UnicodeNormalization;
+
+/// How many levels of a collation element are compared. Lower strengths
+/// ignore differences carried only in later levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strength {
+    /// Base letter identity only: accent and case insensitive.
+    Primary,
+    /// Base letter + accent: case insensitive.
+    Secondary,
+    /// Base letter + accent + case: full distinction.
+    Tertiary,
+}
+
+/// Maps a legacy `sort_order` name to the strength it now stands in for.
+pub fn strength_from_sort_order(sort_order: &str) -> Strength {
+    match sort_order {
+        "ascii_ci" | "unicode_base" | "unicode_base_ci"
+        | "unicode_ci_no_space" | "unicode_ci_no_space_base" => Strength::Primary,
+        // "unicode_ci" was case-insensitive but accent-sensitive (`to_lowercase`
+        // never touched diacritics): Secondary keeps that distinction, unlike
+        // Primary which would also fold "café" and "cafe" together.
+        "unicode_ci" | "unicode_no_space" | "unicode_no_punct" => Strength::Secondary,
+        _ => Strength::Tertiary,
+    }
+}
+
+/// A collation element: up to three independent weights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct Element {
+    primary: u32,
+    secondary: u32,
+    tertiary: u32,
+}
+
+const STEP: u32 = 2;
+
+/// Spacing between the default primary weights of two consecutive code
+/// points, left open so a tailoring rule can insert a run of reassigned
+/// letters between them (via `STEP`) without its weights ever climbing
+/// high enough to collide with the next code point's default weight.
+const PRIMARY_SCALE: u32 = 1024;
+
+/// The default, untailored weight for a single character: base letters
+/// collate by their lowercase code point with case carried at the tertiary
+/// level, and combining marks are primary-ignorable, carrying their accent
+/// identity at the secondary level.
+fn default_element(ch: char) -> Element {
+    if get_general_category(ch) == GeneralCategory::NonspacingMark {
+        Element { primary: 0, secondary: ch as u32, tertiary: 0 }
+    } else {
+        let lower = ch.to_lowercase().next().unwrap_or(ch);
+        let tertiary = if ch.is_uppercase() { 1 } else { 0 };
+        Element { primary: lower as u32 * PRIMARY_SCALE, secondary: 0, tertiary }
+    }
+}
+
+fn default_element_for(text: &str) -> Element {
+    text.chars().next().map(default_element).unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Level {
+    Anchor,
+    Primary,
+    Secondary,
+    Tertiary,
+}
+
+/// Splits a tailoring rule (`"a < b < ä"`, `"e << é"`) into its anchor and
+/// each subsequent grapheme tagged with the `<`/`<<`/`<<<` level that
+/// introduced it. Graphemes are NFD-normalized to match the compared text.
+fn tokenize_rule(rule: &str) -> Vec<(Level, String)> {
+    let chars: Vec<char> = rule.chars().collect();
+    let mut tokens = Vec::new();
+    let mut level = Level::Anchor;
+    let mut buf = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        if chars[i] == '<' {
+            let trimmed = buf.trim();
+            if !trimmed.is_empty() {
+                tokens.push((level, trimmed.nfd().collect::<String>()));
+            }
+            buf.clear();
+
+            let mut depth = 0;
+            while i < chars.len() && chars[i] == '<' {
+                depth += 1;
+                i += 1;
+            }
+            level = match depth {
+                1 => Level::Primary,
+                2 => Level::Secondary,
+                _ => Level::Tertiary,
+            };
+        } else {
+            buf.push(chars[i]);
+            i += 1;
+        }
+    }
+
+    let trimmed = buf.trim();
+    if !trimmed.is_empty() {
+        tokens.push((level, trimmed.nfd().collect::<String>()));
+    }
+
+    tokens
+}
+
+/// A per-locale collation table: tailored element weights layered on top of
+/// the Unicode default table, plus the contraction graphemes (multi-char
+/// elements such as Spanish "ch") those tailorings introduced.
+#[derive(Debug, Clone, Default)]
+pub struct CollationTable {
+    overrides: HashMap<String, Element>,
+    contractions: Vec<String>,
+}
+
+impl CollationTable {
+    pub fn build(tailoring: &[String]) -> Self {
+        let mut table = Self::default();
+        for rule in tailoring {
+            table.apply_rule(rule);
+        }
+        table
+    }
+
+    fn apply_rule(&mut self, rule: &str) {
+        let tokens = tokenize_rule(rule);
+        let Some((_, anchor_text)) = tokens.first() else { return };
+
+        let mut current = self.overrides.get(anchor_text).copied().unwrap_or_else(|| default_element_for(anchor_text));
+        self.overrides.insert(anchor_text.clone(), current);
+        if anchor_text.chars().count() > 1 {
+            self.add_contraction(anchor_text.clone());
+        }
+
+        for (level, text) in &tokens[1..] {
+            let next = match level {
+                Level::Primary => Element { primary: current.primary + STEP, secondary: 0, tertiary: 0 },
+                Level::Secondary => Element { primary: current.primary, secondary: current.secondary + STEP, tertiary: 0 },
+                Level::Tertiary => Element { primary: current.primary, secondary: current.secondary, tertiary: current.tertiary + STEP },
+                Level::Anchor => continue,
+            };
+
+            self.overrides.insert(text.clone(), next);
+            if text.chars().count() > 1 {
+                self.add_contraction(text.clone());
+            }
+            current = next;
+        }
+    }
+
+    fn add_contraction(&mut self, text: String) {
+        if !self.contractions.contains(&text) {
+            self.contractions.push(text);
+            self.contractions.sort_by_key(|s| std::cmp::Reverse(s.chars().count()));
+        }
+    }
+
+    fn element_for(&self, grapheme: &str) -> Element {
+        self.overrides.get(grapheme).copied().unwrap_or_else(|| default_element_for(grapheme))
+    }
+
+    /// Splits NFD-normalized `text` into collation elements, greedily
+    /// preferring the longest matching contraction at each position.
+    fn elements_for(&self, text: &str) -> Vec<Element> {
+        let normalized: Vec<char> = text.nfd().collect();
+        let mut elements = Vec::with_capacity(normalized.len());
+        let mut i = 0;
+
+        'chars: while i < normalized.len() {
+            for contraction in &self.contractions {
+                let c_chars: Vec<char> = contraction.chars().collect();
+                let end = i + c_chars.len();
+                if end <= normalized.len() && normalized[i..end] == c_chars[..] {
+                    elements.push(self.element_for(contraction));
+                    i = end;
+                    continue 'chars;
+                }
+            }
+
+            elements.push(self.element_for(&normalized[i].to_string()));
+            i += 1;
+        }
+
+        elements
+    }
+
+    /// The multi-level sort key for `text` at the given strength: primary
+    /// weights (ignorables dropped) followed by secondary and, at
+    /// [`Strength::Tertiary`], tertiary weights, each level separated by a
+    /// zero byte so levels can never bleed into one another.
+    pub fn sort_key(&self, text: &str, strength: Strength) -> Vec<u8> {
+        let elements = self.elements_for(text);
+        let mut key = Vec::new();
+
+        for e in &elements {
+            if e.primary != 0 {
+                key.extend_from_slice(&e.primary.to_be_bytes());
+            }
+        }
+
+        if strength != Strength::Primary {
+            key.push(0);
+            for e in &elements {
+                key.extend_from_slice(&e.secondary.to_be_bytes());
+            }
+        }
+
+        if strength == Strength::Tertiary {
+            key.push(0);
+            for e in &elements {
+                key.extend_from_slice(&e.tertiary.to_be_bytes());
+            }
+        }
+
+        key
+    }
+
+    pub fn compare(&self, a: &str, b: &str, strength: Strength) -> std::cmp::Ordering {
+        self.sort_key(a, strength).cmp(&self.sort_key(b, strength))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn legacy_unicode_ci_preset_maps_to_secondary_strength() {
+        assert_eq!(strength_from_sort_order("unicode_ci"), Strength::Secondary);
+    }
+
+    #[test]
+    fn primary_strength_ignores_case_and_accents() {
+        let table = CollationTable::default();
+        assert_eq!(table.compare("Cafe", "cafe", Strength::Primary), Ordering::Equal);
+        assert_eq!(table.compare("cafe", "café", Strength::Primary), Ordering::Equal);
+    }
+
+    #[test]
+    fn secondary_strength_ignores_case_but_keeps_accents() {
+        let table = CollationTable::default();
+        assert_eq!(table.compare("Cafe", "cafe", Strength::Secondary), Ordering::Equal);
+        assert_ne!(table.compare("cafe", "café", Strength::Secondary), Ordering::Equal);
+    }
+
+    #[test]
+    fn tertiary_strength_distinguishes_case() {
+        let table = CollationTable::default();
+        assert_ne!(table.compare("Cafe", "cafe", Strength::Tertiary), Ordering::Equal);
+    }
+
+    #[test]
+    fn tailoring_insert_does_not_collide_with_the_next_letter_default_weight() {
+        let table = CollationTable::build(&["a < b < ñ".to_string()]);
+
+        let tailored_b = table.element_for("b").primary;
+        let default_c = default_element('c').primary;
+        assert_ne!(tailored_b, default_c, "tailored 'b' must not land on default 'c'");
+
+        assert_eq!(table.compare("a", "b", Strength::Primary), Ordering::Less);
+        assert_eq!(table.compare("b", "c", Strength::Primary), Ordering::Less);
+        assert_eq!(table.compare("b", "ñ", Strength::Primary), Ordering::Less);
+    }
+}
@@ -1,4 +1,5 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct DataFormat {
@@ -6,7 +7,10 @@ pub struct DataFormat {
     pub LC_NUMERIC: LC_NUMERIC,
     pub LC_MONETARY: LC_MONETARY,
     pub LC_COLLATE: LC_COLLATE,
-    pub PLURAL_RULES: String,
+    /// Per-category CLDR plural conditions, e.g. `{"one": "i = 1 and v = 0"}`.
+    pub PLURAL_RULES: HashMap<String, String>,
+    #[serde(default)]
+    pub LC_SPELLOUT: LC_SPELLOUT,
 }
 
 #[derive(Deserialize, Debug)]
@@ -35,10 +39,43 @@ pub struct LC_MONETARY {
     pub positive_sign: String,
     pub negative_sign: String,
     pub frac_digits: u8,
+    /// Spoken name of the major currency unit, e.g. "dollar" — used by
+    /// `Locale::format_spellout_currency`.
+    #[serde(default)]
+    pub major_unit_name: String,
+    /// Spoken name of the minor currency unit, e.g. "cent".
+    #[serde(default)]
+    pub minor_unit_name: String,
+}
+
+/// One rule-based spellout rule: the largest `base` not exceeding the
+/// number being spelled out is selected, and its `text` emitted, with
+/// `<<`/`>>` markers replaced by the recursively spelled-out quotient and
+/// remainder of the number divided by `base`.
+#[derive(Deserialize, Debug, Clone)]
+pub struct SpelloutRule {
+    pub base: i64,
+    pub text: String,
+}
+
+/// Named rule-based number spellout rulesets for a locale.
+#[derive(Deserialize, Debug, Clone, Default)]
+pub struct LC_SPELLOUT {
+    #[serde(default)]
+    pub cardinal: Vec<SpelloutRule>,
+    #[serde(default)]
+    pub ordinal: Vec<SpelloutRule>,
+    #[serde(default)]
+    pub currency: Vec<SpelloutRule>,
 }
 
 #[derive(Deserialize, Debug)]
 #[derive(Clone)]
 pub struct LC_COLLATE {
     pub sort_order: String,
+    /// Ordered tailoring rules layered on the default Unicode collation
+    /// table, e.g. `["a < b < ä", "e << é"]`. `<` bumps the primary
+    /// weight, `<<` the secondary, `<<<` the tertiary.
+    #[serde(default)]
+    pub tailoring: Vec<String>,
 }
\ No newline at end of file
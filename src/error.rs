@@ -0,0 +1,83 @@
+use std::cell::RefCell;
+use std::ffi::CString;
+use std::fmt;
+use std::os::raw::c_char;
+
+/// Everything that can go wrong loading locale data or driving this crate
+/// over FFI, instead of panicking the host process.
+#[derive(Debug)]
+pub enum AnLocalesError {
+    Io(std::io::Error),
+    ParseJson(serde_json::Error),
+    ParseToml(toml::de::Error),
+    InvalidUtf8(std::str::Utf8Error),
+    InvalidDate { year: i32, month: u32, day: u32 },
+    UnknownLocale(String),
+    MissingKey(String),
+}
+
+impl fmt::Display for AnLocalesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnLocalesError::Io(e) => write!(f, "io error: {e}"),
+            AnLocalesError::ParseJson(e) => write!(f, "failed to parse JSON: {e}"),
+            AnLocalesError::ParseToml(e) => write!(f, "failed to parse TOML: {e}"),
+            AnLocalesError::InvalidUtf8(e) => write!(f, "invalid UTF-8: {e}"),
+            AnLocalesError::InvalidDate { year, month, day } => {
+                write!(f, "invalid date: {year:04}-{month:02}-{day:02}")
+            }
+            AnLocalesError::UnknownLocale(name) => write!(f, "unknown locale: {name}"),
+            AnLocalesError::MissingKey(key) => write!(f, "missing key: {key}"),
+        }
+    }
+}
+
+impl std::error::Error for AnLocalesError {}
+
+impl From<std::io::Error> for AnLocalesError {
+    fn from(e: std::io::Error) -> Self {
+        AnLocalesError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for AnLocalesError {
+    fn from(e: serde_json::Error) -> Self {
+        AnLocalesError::ParseJson(e)
+    }
+}
+
+impl From<toml::de::Error> for AnLocalesError {
+    fn from(e: toml::de::Error) -> Self {
+        AnLocalesError::ParseToml(e)
+    }
+}
+
+impl From<std::str::Utf8Error> for AnLocalesError {
+    fn from(e: std::str::Utf8Error) -> Self {
+        AnLocalesError::InvalidUtf8(e)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, AnLocalesError>;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Records `err`'s message as this thread's last FFI error.
+pub fn set_last_error(err: &AnLocalesError) {
+    let message = CString::new(err.to_string()).unwrap_or_else(|_| CString::new("anLocales error").unwrap());
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(message));
+}
+
+/// Clears this thread's last FFI error, signalling a successful call.
+pub fn clear_last_error() {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+}
+
+/// A pointer to this thread's last recorded error message, borrowed from
+/// thread-local storage. Valid until the next FFI call on this thread;
+/// callers must not free it with `locale_free_str`.
+pub fn last_error_message_ptr() -> *const c_char {
+    LAST_ERROR.with(|slot| slot.borrow().as_ref().map(|c| c.as_ptr()).unwrap_or(std::ptr::null()))
+}
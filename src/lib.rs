@@ -1,6 +1,17 @@
+mod collate;
 mod data_format;
+mod error;
+mod message;
+mod plural;
+mod spellout;
 mod utils;
 
+pub use collate::Strength;
+pub use error::AnLocalesError;
+pub use message::MessageArg;
+
+use error::Result;
+
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -8,58 +19,111 @@ use std::ffi::{CStr, CString};
 use std::fs::{self, File};
 use std::os::raw::c_char;
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
 use std::cmp::Ordering;
-use unicode_normalization::UnicodeNormalization;
-use unicode_general_category::{get_general_category, GeneralCategory};
 
 #[derive(Deserialize, Debug, Serialize)]
 pub struct Settings {
     pub default_locale: String,
     pub fallback_locale: String,
+    /// Explicit lookup chain to consult after the requested locale
+    /// (e.g. `["pt", "en_US"]`). When absent, the chain is
+    /// derived automatically by stripping region subtags.
+    #[serde(default)]
+    pub fallback_chain: Option<Vec<String>>,
 }
 
+/// A `locale.toml` entry: either a plain string, or a plural entry keyed by
+/// CLDR category name (`{one = "...", other = "..."}`).
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum StringEntry {
+    Single(String),
+    Plural(HashMap<String, String>),
+}
 
 #[derive(Clone)]
 pub struct Locale {
     pub data: data_format::DataFormat,
-    pub strings: HashMap<String, Vec<String>>,
-    pub plural_fn: Arc<dyn Fn(u32) -> usize + Send + Sync>,
+    pub strings: HashMap<String, StringEntry>,
+    pub plural_rules: HashMap<String, plural::PluralRule>,
+    collation: collate::CollationTable,
     pub name: String,
 }
 
 impl Locale {
-    pub fn load(path: &Path, name: &str) -> Self {
-        let data_file = File::open(path.join("data_format.json")).expect("data_format.json not found");
-        let data: data_format::DataFormat = serde_json::from_reader(data_file).expect("Failed to parse data_format.json");
+    pub fn load(path: &Path, name: &str) -> Result<Self> {
+        if !path.exists() {
+            return Err(AnLocalesError::UnknownLocale(name.to_string()));
+        }
+
+        let data_file = File::open(path.join("data_format.json"))?;
+        let data: data_format::DataFormat = serde_json::from_reader(data_file)?;
 
-        let toml_str = fs::read_to_string(path.join("locale.toml")).expect("locale.toml not found");
-        let strings: HashMap<String, Vec<String>> = toml::from_str(&toml_str).expect("Failed to parse locale.toml");
+        let toml_str = fs::read_to_string(path.join("locale.toml"))?;
+        let strings: HashMap<String, StringEntry> = toml::from_str(&toml_str)?;
 
-        let plural_rule = data.PLURAL_RULES.clone();
-        let plural_fn = Arc::new(move |n: u32| {
-            let expr = plural_rule.replace("n", &n.to_string());
-            meval::eval_str(&expr).unwrap_or(0.0) as usize
-        });
+        let plural_rules = plural::parse_rules(&data.PLURAL_RULES);
+        let collation = collate::CollationTable::build(&data.LC_COLLATE.tailoring);
 
-        Self { data, strings, plural_fn, name: name.to_string() }
+        Ok(Self { data, strings, plural_rules, collation, name: name.to_string() })
     }
 
     pub fn t<'a>(&'a self, key: &'a str) -> &'a str {
-        self.strings.get(key).and_then(|v| v.get(0)).map(|s| s.as_str()).unwrap_or(key)
+        match self.strings.get(key) {
+            Some(StringEntry::Single(s)) => s.as_str(),
+            Some(StringEntry::Plural(forms)) => forms.get("other").map(|s| s.as_str()).unwrap_or(key),
+            None => key,
+        }
     }
 
-    pub fn plural_word<'a>(&'a self, key: &'a str, n: u32) -> &'a str {
-        if let Some(forms) = self.strings.get(key) {
-            let idx = (self.plural_fn)(n);
-            &forms[std::cmp::min(idx, forms.len() - 1)]
+    /// Like [`t`](Self::t), but reports a missing key instead of silently
+    /// falling back to it.
+    pub fn try_t<'a>(&'a self, key: &'a str) -> Result<&'a str> {
+        if self.strings.contains_key(key) {
+            Ok(self.t(key))
         } else {
-            key
+            Err(AnLocalesError::MissingKey(key.to_string()))
         }
     }
 
-    pub fn format_date(&self, date: Option<NaiveDate>) -> String {
-        date.unwrap().format(&self.data.LC_TIME.date_fmt).to_string()
+    /// Picks the plural category for an integer count and looks up its form.
+    pub fn plural_word<'a>(&'a self, key: &'a str, n: u32) -> &'a str {
+        let operands = plural::PluralOperands::from_integer(n);
+        self.plural_word_for(key, &operands)
+    }
+
+    /// Like [`plural_word`](Self::plural_word), but for decimal counts
+    /// (e.g. "1.5 hours"), selecting the category from the fractional
+    /// operands as well as the integer ones.
+    pub fn plural_word_decimal<'a>(&'a self, key: &'a str, n: f64) -> &'a str {
+        let operands = plural::PluralOperands::from_decimal(n);
+        self.plural_word_for(key, &operands)
+    }
+
+    fn plural_word_for<'a>(&'a self, key: &'a str, operands: &plural::PluralOperands) -> &'a str {
+        let forms = match self.strings.get(key) {
+            Some(StringEntry::Plural(forms)) => forms,
+            Some(StringEntry::Single(s)) => return s.as_str(),
+            None => return key,
+        };
+
+        let category = plural::select_category(&self.plural_rules, operands);
+        forms
+            .get(category.as_str())
+            .or_else(|| forms.get(plural::PluralCategory::Other.as_str()))
+            .map(|s| s.as_str())
+            .unwrap_or(key)
+    }
+
+    /// Formats `key`'s pattern as an ICU-MessageFormat-like message,
+    /// substituting `{name}` placeholders and evaluating `plural`/`select`
+    /// arms against `args`.
+    pub fn format_message(&self, key: &str, args: &HashMap<String, MessageArg>) -> String {
+        message::format(self, key, args)
+    }
+
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        date.format(&self.data.LC_TIME.date_fmt).to_string()
     }
 
     pub fn format_money(&self, amount: f64) -> String {
@@ -171,74 +235,53 @@ impl Locale {
         format!("{}{}", int_str, frac_str)
     }
 
-    pub fn compare(&self, a: &str, b: &str) -> i32 {
-        let ordering: Ordering = match self.data.LC_COLLATE.sort_order.as_str() {
-            "unicode" => a.cmp(b),
-            "ascii" => a.bytes().cmp(b.bytes()),
-            "unicode_ci" => a.to_lowercase().cmp(&b.to_lowercase()),
-            "ascii_ci" => a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase()),
-
-            "unicode_base" => {
-                let a_base: String = a.nfd()
-                    .filter(|c| get_general_category(*c) != GeneralCategory::NonspacingMark)
-                    .collect();
-                let b_base: String = b.nfd()
-                    .filter(|c| get_general_category(*c) != GeneralCategory::NonspacingMark)
-                    .collect();
-                a_base.cmp(&b_base)
-            },
-
-            "unicode_base_ci" => {
-                let a_base: String = a.nfd()
-                    .filter(|c| get_general_category(*c) != GeneralCategory::NonspacingMark)
-                    .collect::<String>()
-                    .to_lowercase();
-                let b_base: String = b.nfd()
-                    .filter(|c| get_general_category(*c) != GeneralCategory::NonspacingMark)
-                    .collect::<String>()
-                    .to_lowercase();
-                a_base.cmp(&b_base)
-            },
-
-            "unicode_no_space" => {
-                let a_clean: String = a.chars().filter(|c| !c.is_whitespace()).collect();
-                let b_clean: String = b.chars().filter(|c| !c.is_whitespace()).collect();
-                a_clean.cmp(&b_clean)
-            },
+    /// Spells `number` out as words using the locale's rule-based cardinal
+    /// spellout ruleset, e.g. "1,234" -> "one thousand two hundred
+    /// thirty-four".
+    pub fn format_spellout(&self, number: f64) -> String {
+        spellout::spellout(&self.data.LC_SPELLOUT.cardinal, number.round() as i64)
+    }
 
-            "unicode_no_punct" => {
-                let a_clean: String = a.chars().filter(|c| !c.is_ascii_punctuation()).collect();
-                let b_clean: String = b.chars().filter(|c| !c.is_ascii_punctuation()).collect();
-                a_clean.cmp(&b_clean)
-            },
+    /// Like [`format_spellout`](Self::format_spellout), but using the
+    /// ordinal ruleset ("1st" -> "first").
+    pub fn format_spellout_ordinal(&self, number: f64) -> String {
+        spellout::spellout(&self.data.LC_SPELLOUT.ordinal, number.round() as i64)
+    }
 
-            "unicode_ci_no_space" => {
-                let a_clean: String = a.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
-                let b_clean: String = b.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
-                a_clean.cmp(&b_clean)
-            },
+    /// Spells a monetary amount out as words, combining the integer part
+    /// with the major currency unit name and the fractional part with the
+    /// minor unit name (for cheque printing and TTS/accessibility output).
+    pub fn format_spellout_currency(&self, amount: f64) -> String {
+        let ruleset = if self.data.LC_SPELLOUT.currency.is_empty() {
+            &self.data.LC_SPELLOUT.cardinal
+        } else {
+            &self.data.LC_SPELLOUT.currency
+        };
 
-            "unicode_ci_no_space_base" => {
-                let a_clean: String = a.nfd()
-                    .filter(|c| get_general_category(*c) != GeneralCategory::NonspacingMark && !c.is_whitespace())
-                    .collect::<String>()
-                    .to_lowercase();
-                let b_clean: String = b.nfd()
-                    .filter(|c| get_general_category(*c) != GeneralCategory::NonspacingMark && !c.is_whitespace())
-                    .collect::<String>()
-                    .to_lowercase();
-                a_clean.cmp(&b_clean)
-            },
+        spellout::spellout_currency(ruleset, amount, &self.data.LC_MONETARY.major_unit_name, &self.data.LC_MONETARY.minor_unit_name)
+    }
 
-            _ => a.cmp(b),
-        };
+    /// Compares `a` and `b` using the locale's tailored collation table at
+    /// the strength implied by its legacy `sort_order` preset.
+    pub fn compare(&self, a: &str, b: &str) -> i32 {
+        let strength = collate::strength_from_sort_order(&self.data.LC_COLLATE.sort_order);
+        self.compare_with_strength(a, b, strength)
+    }
 
-        match ordering {
+    /// Compares `a` and `b` at an explicit collation [`Strength`](collate::Strength).
+    pub fn compare_with_strength(&self, a: &str, b: &str, strength: collate::Strength) -> i32 {
+        match self.collation.compare(a, b, strength) {
             Ordering::Less => -1,
             Ordering::Equal => 0,
             Ordering::Greater => 1,
         }
     }
+
+    /// The multi-level collation sort key for `s`, at full (tertiary)
+    /// strength, suitable for cheaply presorting large lists.
+    pub fn sort_key(&self, s: &str) -> Vec<u8> {
+        self.collation.sort_key(s, collate::Strength::Tertiary)
+    }
 }
 
 pub struct AnLocales {
@@ -249,51 +292,112 @@ pub struct AnLocales {
 }
 
 impl AnLocales {
-    fn _new(locales_path : PathBuf, temp_path : PathBuf, settings_file_path : PathBuf) -> Self {
-        // hook for panic
-        std::panic::set_hook(Box::new(|info| {
-            eprintln!("panic happened: {}", info);
-        }));
-
-        // init
-        fs::create_dir_all(&locales_path).expect("failed to create locales dir");
-        fs::create_dir_all(&temp_path).expect("failed to create temp dir");
-        utils::ensure_that_config_exists(settings_file_path.clone());
+    fn _new(locales_path : PathBuf, temp_path : PathBuf, settings_file_path : PathBuf) -> Result<Self> {
+        fs::create_dir_all(&locales_path)?;
+        fs::create_dir_all(&temp_path)?;
+        utils::ensure_that_config_exists(settings_file_path.clone())?;
 
         // opening and parsing settings.json
-        let settings_file = File::open(&settings_file_path).expect("settings.json not found");
-        let settings: Settings = serde_json::from_reader(settings_file).expect("Failed to parse settings.json");
+        let settings_file = File::open(&settings_file_path)?;
+        let settings: Settings = serde_json::from_reader(settings_file)?;
 
-        Self { locales_path, temp_path, settings, cache: HashMap::new() }
+        Ok(Self { locales_path, temp_path, settings, cache: HashMap::new() })
     }
 
-    pub fn new() -> Self {
+    pub fn new() -> Result<Self> {
         // directory
         let (locales_path, temp_path, settings_file_path) = utils::default_paths();
         Self::_new(locales_path, temp_path, settings_file_path)
     }
 
-    pub fn new_with_paths(locales_path : PathBuf, temp_path : PathBuf, settings_file_path : PathBuf) -> Self {
+    pub fn new_with_paths(locales_path : PathBuf, temp_path : PathBuf, settings_file_path : PathBuf) -> Result<Self> {
         Self::_new(locales_path, temp_path, settings_file_path)
     }
 
-    pub fn load_locale(&mut self, name: &str) -> &Locale {
+    pub fn load_locale(&mut self, name: &str) -> Result<&Locale> {
         if !self.cache.contains_key(name) {
-            let locale = Locale::load(&self.locales_path.join(name), name);
+            let locale = Locale::load(&self.locales_path.join(name), name)?;
             self.cache.insert(name.to_string(), locale);
         }
-        self.cache.get(name).unwrap()
+        Ok(self.cache.get(name).expect("just inserted"))
     }
 
-    pub fn default_locale(&mut self) -> &Locale {
+    pub fn default_locale(&mut self) -> Result<&Locale> {
         let name = self.settings.default_locale.clone();
         self.load_locale(&name)
     }
 
-    pub fn fallback_locale(&mut self) -> &Locale {
+    pub fn fallback_locale(&mut self) -> Result<&Locale> {
         let name = self.settings.fallback_locale.clone();
         self.load_locale(&*name)
     }
+
+    /// Builds the chain of locale names to consult for `locale_name`: by
+    /// default the locale itself, then its region-stripped parents
+    /// (`pt_BR` -> `pt`), then the configured fallback locale, then the
+    /// default locale. `settings.fallback_chain`, if set, replaces
+    /// everything after the requested locale.
+    fn fallback_chain_for(&self, locale_name: &str) -> Vec<String> {
+        if let Some(explicit) = &self.settings.fallback_chain {
+            let mut chain = vec![locale_name.to_string()];
+            chain.extend(explicit.iter().cloned());
+            return chain;
+        }
+
+        let mut chain = Vec::new();
+        let mut current = locale_name.to_string();
+        loop {
+            if !chain.contains(&current) {
+                chain.push(current.clone());
+            }
+            match current.rsplit_once('_') {
+                Some((parent, _)) => current = parent.to_string(),
+                None => break,
+            }
+        }
+
+        for name in [self.settings.fallback_locale.clone(), self.settings.default_locale.clone()] {
+            if !chain.contains(&name) {
+                chain.push(name);
+            }
+        }
+
+        chain
+    }
+
+    /// Looks up `key` in `locale_name`, walking its fallback chain until a
+    /// locale defines the key. Returns the bare key if none of them do.
+    pub fn t_with_fallback(&mut self, locale_name: &str, key: &str) -> String {
+        for name in self.fallback_chain_for(locale_name) {
+            if let Ok(locale) = self.load_locale(&name) {
+                if locale.strings.contains_key(key) {
+                    return locale.t(key).to_string();
+                }
+            }
+        }
+        key.to_string()
+    }
+}
+
+/// Converts a `String` to an owned C string, returning null instead of
+/// panicking if it contains an interior NUL byte.
+fn cstring_or_null(s: String) -> *const c_char {
+    match CString::new(s) {
+        Ok(c) => c.into_raw(),
+        Err(_) => std::ptr::null(),
+    }
+}
+
+/// Reads a borrowed `&str` out of a C string, recording an `InvalidUtf8`
+/// FFI error instead of panicking if it isn't valid UTF-8.
+unsafe fn cstr_to_str<'a>(ptr: *const c_char) -> Option<&'a str> {
+    match unsafe { CStr::from_ptr(ptr) }.to_str() {
+        Ok(s) => Some(s),
+        Err(e) => {
+            error::set_last_error(&AnLocalesError::InvalidUtf8(e));
+            None
+        }
+    }
 }
 
 // ================= C API =================
@@ -311,18 +415,36 @@ pub extern "C" fn anlocales_new_with_paths(
     let temp_path = unsafe { CStr::from_ptr(temp_path).to_string_lossy().into_owned() };
     let settings_file_path = unsafe { CStr::from_ptr(settings_file_path).to_string_lossy().into_owned() };
 
-    let al = AnLocales::new_with_paths(
+    let result = AnLocales::new_with_paths(
         PathBuf::from(locales_path),
         PathBuf::from(temp_path),
         PathBuf::from(settings_file_path),
     );
 
-    Box::into_raw(Box::new(al))
+    match result {
+        Ok(al) => {
+            error::clear_last_error();
+            Box::into_raw(Box::new(al))
+        }
+        Err(e) => {
+            error::set_last_error(&e);
+            std::ptr::null_mut()
+        }
+    }
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn anlocales_new() -> *mut AnLocales {
-    Box::into_raw(Box::new(AnLocales::new()))
+    match AnLocales::new() {
+        Ok(al) => {
+            error::clear_last_error();
+            Box::into_raw(Box::new(al))
+        }
+        Err(e) => {
+            error::set_last_error(&e);
+            std::ptr::null_mut()
+        }
+    }
 }
 
 #[unsafe(no_mangle)]
@@ -336,8 +458,16 @@ pub unsafe extern "C" fn anlocales_default_locale(ptr: *mut AnLocales) -> *mut L
     unsafe {
         if ptr.is_null() { return std::ptr::null_mut(); }
         let al = &mut *ptr;
-        let locale = al.default_locale();
-        Box::into_raw(Box::new(locale.clone()))
+        match al.default_locale() {
+            Ok(locale) => {
+                error::clear_last_error();
+                Box::into_raw(Box::new(locale.clone()))
+            }
+            Err(e) => {
+                error::set_last_error(&e);
+                std::ptr::null_mut()
+            }
+        }
     }
 }
 
@@ -346,20 +476,50 @@ pub unsafe extern "C" fn anlocales_fallback_locale(ptr: *mut AnLocales) -> *mut
     unsafe {
         if ptr.is_null() { return std::ptr::null_mut(); }
         let al = &mut *ptr;
-        let locale = al.fallback_locale();
-        Box::into_raw(Box::new(locale.clone()))
+        match al.fallback_locale() {
+            Ok(locale) => {
+                error::clear_last_error();
+                Box::into_raw(Box::new(locale.clone()))
+            }
+            Err(e) => {
+                error::set_last_error(&e);
+                std::ptr::null_mut()
+            }
+        }
     }
 }
 
+#[unsafe(no_mangle)]
+pub extern "C" fn anlocales_t_fallback(ptr: *mut AnLocales, locale: *const c_char, key: *const c_char) -> *const c_char {
+    if ptr.is_null() || locale.is_null() || key.is_null() { return std::ptr::null(); }
+    let al = unsafe { &mut *ptr };
+    let Some(locale_str) = (unsafe { cstr_to_str(locale) }) else { return std::ptr::null(); };
+    let Some(key_str) = (unsafe { cstr_to_str(key) }) else { return std::ptr::null(); };
+    let value = al.t_with_fallback(locale_str, key_str);
+    error::clear_last_error();
+    cstring_or_null(value)
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn anlocales_last_error_message() -> *const c_char {
+    error::last_error_message_ptr()
+}
 
 #[unsafe(no_mangle)]
 pub extern "C" fn locale_load(ptr: *mut AnLocales, name: *const c_char) -> *mut Locale {
     if ptr.is_null() || name.is_null() { return std::ptr::null_mut(); }
-    let cstr = unsafe { CStr::from_ptr(name) };
-    let name_str = cstr.to_str().unwrap();
+    let Some(name_str) = (unsafe { cstr_to_str(name) }) else { return std::ptr::null_mut(); };
     let al = unsafe { &mut *ptr };
-    let locale = al.load_locale(name_str);
-    Box::into_raw(Box::new(locale.clone()))
+    match al.load_locale(name_str) {
+        Ok(locale) => {
+            error::clear_last_error();
+            Box::into_raw(Box::new(locale.clone()))
+        }
+        Err(e) => {
+            error::set_last_error(&e);
+            std::ptr::null_mut()
+        }
+    }
 }
 
 #[unsafe(no_mangle)]
@@ -371,54 +531,141 @@ pub extern "C" fn locale_free(ptr: *mut Locale) {
 #[unsafe(no_mangle)]
 pub extern "C" fn locale_t(ptr: *mut Locale, key: *const c_char) -> *const c_char {
     if ptr.is_null() || key.is_null() { return std::ptr::null(); }
-    let cstr = unsafe { CStr::from_ptr(key) };
-    let key_str = cstr.to_str().unwrap();
+    let Some(key_str) = (unsafe { cstr_to_str(key) }) else { return std::ptr::null(); };
     let locale = unsafe { &*ptr };
-    let val = locale.t(key_str);
-    CString::new(val).unwrap().into_raw()
+    error::clear_last_error();
+    cstring_or_null(locale.t(key_str).to_string())
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn locale_format_date(ptr: *mut Locale, year: i32, month: u32, day: u32) -> *const c_char {
     if ptr.is_null() { return std::ptr::null(); }
     let locale = unsafe { &*ptr };
-    let date = NaiveDate::from_ymd_opt(year, month, day);
-    let s = locale.format_date(date);
-    CString::new(s).unwrap().into_raw()
+    let Some(date) = NaiveDate::from_ymd_opt(year, month, day) else {
+        error::set_last_error(&AnLocalesError::InvalidDate { year, month, day });
+        return std::ptr::null();
+    };
+    error::clear_last_error();
+    cstring_or_null(locale.format_date(date))
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn locale_format_money(ptr: *mut Locale, amount: f64) -> *const c_char {
     if ptr.is_null() { return std::ptr::null(); }
     let locale = unsafe { &*ptr };
-    let s = locale.format_money(amount);
-    CString::new(s).unwrap().into_raw()
+    error::clear_last_error();
+    cstring_or_null(locale.format_money(amount))
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn locale_format_numeric(ptr: *mut Locale, number: f64) -> *const c_char {
     if ptr.is_null() { return std::ptr::null(); }
     let locale = unsafe { &*ptr };
-    let s = locale.format_numeric(number);
-    CString::new(s).unwrap().into_raw()
+    error::clear_last_error();
+    cstring_or_null(locale.format_numeric(number))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn locale_format_spellout(ptr: *mut Locale, number: f64) -> *const c_char {
+    if ptr.is_null() { return std::ptr::null(); }
+    let locale = unsafe { &*ptr };
+    error::clear_last_error();
+    cstring_or_null(locale.format_spellout(number))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn locale_format_spellout_ordinal(ptr: *mut Locale, number: f64) -> *const c_char {
+    if ptr.is_null() { return std::ptr::null(); }
+    let locale = unsafe { &*ptr };
+    error::clear_last_error();
+    cstring_or_null(locale.format_spellout_ordinal(number))
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn locale_format_spellout_currency(ptr: *mut Locale, amount: f64) -> *const c_char {
+    if ptr.is_null() { return std::ptr::null(); }
+    let locale = unsafe { &*ptr };
+    error::clear_last_error();
+    cstring_or_null(locale.format_spellout_currency(amount))
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn locale_compare(ptr: *mut Locale, a: *const c_char, b: *const c_char) -> i32 {
     if ptr.is_null() || a.is_null() || b.is_null() { return 0; }
-    let s1 = unsafe { CStr::from_ptr(a).to_str().unwrap_or("") };
-    let s2 = unsafe { CStr::from_ptr(b).to_str().unwrap_or("") };
+    let Some(s1) = (unsafe { cstr_to_str(a) }) else { return 0; };
+    let Some(s2) = (unsafe { cstr_to_str(b) }) else { return 0; };
     let locale = unsafe { &*ptr };
+    error::clear_last_error();
     locale.compare(s1, s2)
 }
 
 #[unsafe(no_mangle)]
 pub extern "C" fn locale_plural_word(ptr: *mut Locale, key: *const c_char, n: u32) -> *const c_char {
+    if ptr.is_null() || key.is_null() { return std::ptr::null(); }
+    let Some(key_str) = (unsafe { cstr_to_str(key) }) else { return std::ptr::null(); };
+    let locale = unsafe { &*ptr };
+    error::clear_last_error();
+    cstring_or_null(locale.plural_word(key_str, n).to_string())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn locale_plural_word_decimal(ptr: *mut Locale, key: *const c_char, n: f64) -> *const c_char {
+    if ptr.is_null() || key.is_null() { return std::ptr::null(); }
+    let Some(key_str) = (unsafe { cstr_to_str(key) }) else { return std::ptr::null(); };
+    let locale = unsafe { &*ptr };
+    error::clear_last_error();
+    cstring_or_null(locale.plural_word_decimal(key_str, n).to_string())
+}
+
+/// Type tag for a `locale_format_message` argument; the matching
+/// `arg_values` entry is always passed as a C string and parsed per-tag.
+#[repr(C)]
+pub enum MessageArgType {
+    Str = 0,
+    Int = 1,
+    Float = 2,
+    Date = 3,
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn locale_format_message(
+    ptr: *mut Locale,
+    key: *const c_char,
+    arg_names: *const *const c_char,
+    arg_types: *const i32,
+    arg_values: *const *const c_char,
+    arg_count: usize,
+) -> *const c_char {
     if ptr.is_null() || key.is_null() { return std::ptr::null(); }
     let locale = unsafe { &*ptr };
-    let key_str = unsafe { CStr::from_ptr(key) }.to_str().unwrap();
-    let word = locale.plural_word(key_str, n);
-    CString::new(word).unwrap().into_raw()
+    let Some(key_str) = (unsafe { cstr_to_str(key) }) else { return std::ptr::null(); };
+
+    let mut args = HashMap::new();
+    if !arg_names.is_null() && !arg_types.is_null() && !arg_values.is_null() {
+        for i in 0..arg_count {
+            let name_ptr = unsafe { *arg_names.add(i) };
+            let value_ptr = unsafe { *arg_values.add(i) };
+            if name_ptr.is_null() || value_ptr.is_null() { continue; }
+
+            let name = unsafe { CStr::from_ptr(name_ptr) }.to_string_lossy().into_owned();
+            let value = unsafe { CStr::from_ptr(value_ptr) }.to_string_lossy().into_owned();
+            let tag = unsafe { *arg_types.add(i) };
+
+            let arg = match tag {
+                1 => value.parse::<i64>().ok().map(MessageArg::Int),
+                2 => value.parse::<f64>().ok().map(MessageArg::Float),
+                3 => NaiveDate::parse_from_str(&value, "%Y-%m-%d").ok().map(MessageArg::Date),
+                _ => Some(MessageArg::Str(value)),
+            };
+
+            if let Some(arg) = arg {
+                args.insert(name, arg);
+            }
+        }
+    }
+
+    error::clear_last_error();
+    cstring_or_null(locale.format_message(key_str, &args))
 }
 
 #[unsafe(no_mangle)]
@@ -426,3 +673,116 @@ pub extern "C" fn locale_free_str(s: *mut c_char) {
     if s.is_null() { return; }
     unsafe { let _ = CString::from_raw(s); }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_format::{LC_COLLATE, LC_MONETARY, LC_NUMERIC, LC_SPELLOUT, LC_TIME};
+
+    fn unique_temp_dir(label: &str) -> PathBuf {
+        let pid = std::process::id();
+        std::env::temp_dir().join(format!("anlocales_test_{label}_{pid}"))
+    }
+
+    /// Minimal valid `data_format.json` contents, enough to satisfy every
+    /// required field of [`data_format::DataFormat`].
+    const MINIMAL_DATA_FORMAT_JSON: &str = r#"{
+        "LC_TIME": {"days": [], "months": [], "date_fmt": "%Y-%m-%d"},
+        "LC_NUMERIC": {"decimal_point": ".", "thousands_sep": ",", "grouping": [3]},
+        "LC_MONETARY": {
+            "currency_symbol": "$",
+            "int_curr_symbol": "USD",
+            "mon_decimal_point": ".",
+            "mon_thousands_sep": ",",
+            "positive_sign": "",
+            "negative_sign": "-",
+            "frac_digits": 2
+        },
+        "LC_COLLATE": {"sort_order": "unicode"},
+        "PLURAL_RULES": {}
+    }"#;
+
+    fn test_locale() -> Locale {
+        let data = data_format::DataFormat {
+            LC_TIME: LC_TIME { days: vec![], months: vec![], date_fmt: "%Y-%m-%d".into() },
+            LC_NUMERIC: LC_NUMERIC { decimal_point: ".".into(), thousands_sep: ",".into(), grouping: vec![3] },
+            LC_MONETARY: LC_MONETARY {
+                currency_symbol: "$".into(),
+                int_curr_symbol: "USD".into(),
+                mon_decimal_point: ".".into(),
+                mon_thousands_sep: ",".into(),
+                positive_sign: "".into(),
+                negative_sign: "-".into(),
+                frac_digits: 2,
+                major_unit_name: "dollar".into(),
+                minor_unit_name: "cent".into(),
+            },
+            LC_COLLATE: LC_COLLATE { sort_order: "unicode".into(), tailoring: vec![] },
+            PLURAL_RULES: HashMap::new(),
+            LC_SPELLOUT: LC_SPELLOUT::default(),
+        };
+
+        Locale { data, strings: HashMap::new(), plural_rules: HashMap::new(), collation: collate::CollationTable::default(), name: "test".into() }
+    }
+
+    #[test]
+    fn load_missing_locale_dir_returns_unknown_locale_error() {
+        let path = unique_temp_dir("missing_dir");
+        let _ = fs::remove_dir_all(&path);
+
+        let err = Locale::load(&path, "xx").unwrap_err();
+        assert!(matches!(err, AnLocalesError::UnknownLocale(name) if name == "xx"));
+    }
+
+    #[test]
+    fn load_malformed_locale_toml_returns_parse_toml_error() {
+        let dir = unique_temp_dir("malformed_toml");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("data_format.json"), MINIMAL_DATA_FORMAT_JSON).unwrap();
+        fs::write(dir.join("locale.toml"), "not = [valid toml").unwrap();
+
+        let err = Locale::load(&dir, "bad").unwrap_err();
+        assert!(matches!(err, AnLocalesError::ParseToml(_)));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn anlocales_new_with_paths_with_malformed_settings_returns_null_and_sets_last_error() {
+        let base = unique_temp_dir("bad_settings");
+        let locales = base.join("locales");
+        let temp = base.join("temp");
+        let settings = base.join("settings.json");
+        fs::create_dir_all(&base).unwrap();
+        fs::write(&settings, "not valid json").unwrap();
+
+        let locales_c = CString::new(locales.to_str().unwrap()).unwrap();
+        let temp_c = CString::new(temp.to_str().unwrap()).unwrap();
+        let settings_c = CString::new(settings.to_str().unwrap()).unwrap();
+
+        let ptr = anlocales_new_with_paths(locales_c.as_ptr(), temp_c.as_ptr(), settings_c.as_ptr());
+        assert!(ptr.is_null());
+
+        let err_ptr = anlocales_last_error_message();
+        assert!(!err_ptr.is_null());
+        let msg = unsafe { CStr::from_ptr(err_ptr) }.to_string_lossy().into_owned();
+        assert!(msg.contains("JSON"), "expected a JSON parse error, got: {msg}");
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn locale_format_date_with_invalid_date_returns_null_and_sets_last_error() {
+        let ptr = Box::into_raw(Box::new(test_locale()));
+
+        let result = locale_format_date(ptr, 2024, 2, 30);
+        assert!(result.is_null());
+
+        let err_ptr = anlocales_last_error_message();
+        assert!(!err_ptr.is_null());
+        let msg = unsafe { CStr::from_ptr(err_ptr) }.to_string_lossy().into_owned();
+        assert!(msg.contains("invalid date"), "expected an invalid date error, got: {msg}");
+
+        locale_free(ptr);
+    }
+}
@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::plural::{self, PluralOperands};
+use crate::{Locale, StringEntry};
+
+/// A value bound to a named placeholder in a message pattern.
+#[derive(Debug, Clone)]
+pub enum MessageArg {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Date(NaiveDate),
+}
+
+impl MessageArg {
+    fn display(&self, locale: &Locale) -> String {
+        match self {
+            MessageArg::Str(s) => s.clone(),
+            MessageArg::Int(n) => locale.format_numeric(*n as f64),
+            MessageArg::Float(n) => locale.format_numeric(*n),
+            MessageArg::Date(d) => locale.format_date(*d),
+        }
+    }
+
+    fn as_plural_operands(&self) -> Option<PluralOperands> {
+        match self {
+            MessageArg::Int(n) => Some(PluralOperands::from_integer(n.unsigned_abs() as u32)),
+            MessageArg::Float(n) => Some(PluralOperands::from_decimal(*n)),
+            _ => None,
+        }
+    }
+
+    fn as_selector(&self) -> String {
+        match self {
+            MessageArg::Str(s) => s.clone(),
+            MessageArg::Int(n) => n.to_string(),
+            MessageArg::Float(n) => n.to_string(),
+            MessageArg::Date(d) => d.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Text(String),
+    Placeholder(String),
+    Plural { arg: String, arms: Vec<(String, Vec<Node>)> },
+    Select { arg: String, arms: Vec<(String, Vec<Node>)> },
+}
+
+/// Parses an ICU-MessageFormat-like pattern into a reusable node tree.
+fn parse(pattern: &str) -> Vec<Node> {
+    let chars: Vec<char> = pattern.chars().collect();
+    parse_nodes(&chars, 0, chars.len())
+}
+
+fn parse_nodes(chars: &[char], start: usize, end: usize) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut literal = String::new();
+    let mut i = start;
+
+    while i < end {
+        if chars[i] == '{' {
+            if !literal.is_empty() {
+                nodes.push(Node::Text(std::mem::take(&mut literal)));
+            }
+            let close = find_matching_brace(chars, i, end);
+            nodes.push(parse_placeholder(chars, i + 1, close));
+            i = close + 1;
+        } else {
+            literal.push(chars[i]);
+            i += 1;
+        }
+    }
+
+    if !literal.is_empty() {
+        nodes.push(Node::Text(literal));
+    }
+
+    nodes
+}
+
+/// Returns the index of the `}` matching the `{` at `open_idx`.
+fn find_matching_brace(chars: &[char], open_idx: usize, end: usize) -> usize {
+    let mut depth = 0;
+    let mut i = open_idx;
+    while i < end {
+        match chars[i] {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    end.saturating_sub(1)
+}
+
+fn parse_placeholder(chars: &[char], start: usize, end: usize) -> Node {
+    let (arg, rest) = match split_once_top_level(chars, start, end) {
+        Some(parts) => parts,
+        None => return Node::Placeholder(chars[start..end].iter().collect::<String>().trim().to_string()),
+    };
+
+    let (keyword, arms_src) = match split_once_top_level(chars, rest.0, rest.1) {
+        Some(parts) => parts,
+        None => return Node::Placeholder(arg),
+    };
+
+    let arms = parse_arms(chars, arms_src.0, arms_src.1);
+    match keyword.as_str() {
+        "plural" => Node::Plural { arg, arms },
+        "select" => Node::Select { arg, arms },
+        _ => Node::Placeholder(arg),
+    }
+}
+
+/// Splits `chars[start..end]` on the first top-level comma (braces are not
+/// descended into), returning the trimmed text before it and the `(start,
+/// end)` span of everything after it.
+fn split_once_top_level(chars: &[char], start: usize, end: usize) -> Option<(String, (usize, usize))> {
+    let mut depth = 0;
+    let mut i = start;
+    while i < end {
+        match chars[i] {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                let head: String = chars[start..i].iter().collect::<String>().trim().to_string();
+                return Some((head, (i + 1, end)));
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+fn parse_arms(chars: &[char], start: usize, end: usize) -> Vec<(String, Vec<Node>)> {
+    let mut arms = Vec::new();
+    let mut i = start;
+
+    while i < end {
+        while i < end && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if i >= end {
+            break;
+        }
+
+        let sel_start = i;
+        while i < end && !chars[i].is_whitespace() && chars[i] != '{' {
+            i += 1;
+        }
+        let selector: String = chars[sel_start..i].iter().collect();
+
+        while i < end && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if i >= end || chars[i] != '{' {
+            break;
+        }
+
+        let close = find_matching_brace(chars, i, end);
+        let body = parse_nodes(chars, i + 1, close);
+        arms.push((selector, body));
+        i = close + 1;
+    }
+
+    arms
+}
+
+/// Renders a parsed message pattern against the given arguments.
+fn render(nodes: &[Node], args: &HashMap<String, MessageArg>, locale: &Locale) -> String {
+    let mut out = String::new();
+    render_nodes(nodes, args, locale, None, &mut out);
+    out
+}
+
+fn render_nodes(nodes: &[Node], args: &HashMap<String, MessageArg>, locale: &Locale, hash_value: Option<&str>, out: &mut String) {
+    for node in nodes {
+        match node {
+            Node::Text(text) => match hash_value {
+                Some(n) => out.push_str(&text.replace('#', n)),
+                None => out.push_str(text),
+            },
+            Node::Placeholder(name) => {
+                if let Some(arg) = args.get(name) {
+                    out.push_str(&arg.display(locale));
+                }
+            }
+            Node::Plural { arg, arms } => {
+                let Some(value) = args.get(arg) else { continue };
+                let Some(operands) = value.as_plural_operands() else { continue };
+                let category = plural::select_category(&locale.plural_rules, &operands);
+                let number_text = value.display(locale);
+
+                let body = arms
+                    .iter()
+                    .find(|(selector, _)| selector == category.as_str())
+                    .or_else(|| arms.iter().find(|(selector, _)| selector == "other"))
+                    .map(|(_, body)| body);
+
+                if let Some(body) = body {
+                    render_nodes(body, args, locale, Some(&number_text), out);
+                }
+            }
+            Node::Select { arg, arms } => {
+                let Some(value) = args.get(arg) else { continue };
+                let selector = value.as_selector();
+
+                let body = arms
+                    .iter()
+                    .find(|(arm_selector, _)| *arm_selector == selector)
+                    .or_else(|| arms.iter().find(|(arm_selector, _)| arm_selector == "other"))
+                    .map(|(_, body)| body);
+
+                if let Some(body) = body {
+                    render_nodes(body, args, locale, hash_value, out);
+                }
+            }
+        }
+    }
+}
+
+/// Looks up `key` in the locale and formats it against `args`, reusing the
+/// `other`/plain form of plural entries as the pattern source.
+pub fn format(locale: &Locale, key: &str, args: &HashMap<String, MessageArg>) -> String {
+    let pattern = match locale.strings.get(key) {
+        Some(StringEntry::Single(s)) => s.as_str(),
+        Some(StringEntry::Plural(forms)) => forms.get("other").map(|s| s.as_str()).unwrap_or(key),
+        None => return key.to_string(),
+    };
+
+    render(&parse(pattern), args, locale)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collate::CollationTable;
+    use crate::data_format::{DataFormat, LC_COLLATE, LC_MONETARY, LC_NUMERIC, LC_SPELLOUT, LC_TIME};
+    use crate::plural::PluralRule;
+
+    fn test_locale(strings: HashMap<String, StringEntry>, plural_rules: HashMap<String, PluralRule>) -> Locale {
+        let data = DataFormat {
+            LC_TIME: LC_TIME { days: vec![], months: vec![], date_fmt: "%Y-%m-%d".into() },
+            LC_NUMERIC: LC_NUMERIC { decimal_point: ".".into(), thousands_sep: ",".into(), grouping: vec![3] },
+            LC_MONETARY: LC_MONETARY {
+                currency_symbol: "$".into(),
+                int_curr_symbol: "USD".into(),
+                mon_decimal_point: ".".into(),
+                mon_thousands_sep: ",".into(),
+                positive_sign: "".into(),
+                negative_sign: "-".into(),
+                frac_digits: 2,
+                major_unit_name: "dollar".into(),
+                minor_unit_name: "cent".into(),
+            },
+            LC_COLLATE: LC_COLLATE { sort_order: "unicode".into(), tailoring: vec![] },
+            PLURAL_RULES: HashMap::new(),
+            LC_SPELLOUT: LC_SPELLOUT::default(),
+        };
+
+        Locale { data, strings, plural_rules, collation: CollationTable::default(), name: "test".into() }
+    }
+
+    #[test]
+    fn placeholder_is_substituted() {
+        let locale = test_locale(HashMap::new(), HashMap::new());
+        let mut args = HashMap::new();
+        args.insert("name".to_string(), MessageArg::Str("Ada".to_string()));
+        assert_eq!(render(&parse("Hello, {name}!"), &args, &locale), "Hello, Ada!");
+    }
+
+    #[test]
+    fn plural_arm_picks_category_and_substitutes_hash() {
+        let mut plural_rules = HashMap::new();
+        plural_rules.insert("one".to_string(), PluralRule::parse("i = 1 and v = 0"));
+        let locale = test_locale(HashMap::new(), plural_rules);
+        let nodes = parse("{count, plural, one {# item} other {# items}}");
+
+        let mut args = HashMap::new();
+        args.insert("count".to_string(), MessageArg::Int(1));
+        assert_eq!(render(&nodes, &args, &locale), "1 item");
+
+        args.insert("count".to_string(), MessageArg::Int(5));
+        assert_eq!(render(&nodes, &args, &locale), "5 items");
+    }
+
+    #[test]
+    fn plural_arm_handles_negative_counts() {
+        let mut plural_rules = HashMap::new();
+        plural_rules.insert("one".to_string(), PluralRule::parse("i = 1 and v = 0"));
+        let locale = test_locale(HashMap::new(), plural_rules);
+        let nodes = parse("{count, plural, one {# item} other {# items}}");
+
+        let mut args = HashMap::new();
+        args.insert("count".to_string(), MessageArg::Int(-1));
+        assert_eq!(render(&nodes, &args, &locale), "-1 item");
+    }
+
+    #[test]
+    fn select_arm_picks_matching_selector_or_falls_back_to_other() {
+        let locale = test_locale(HashMap::new(), HashMap::new());
+        let nodes = parse("{gender, select, male {He} female {She} other {They}} left.");
+
+        let mut args = HashMap::new();
+        args.insert("gender".to_string(), MessageArg::Str("female".to_string()));
+        assert_eq!(render(&nodes, &args, &locale), "She left.");
+
+        args.insert("gender".to_string(), MessageArg::Str("unknown".to_string()));
+        assert_eq!(render(&nodes, &args, &locale), "They left.");
+    }
+
+    #[test]
+    fn format_looks_up_key_and_falls_back_to_the_bare_key_when_missing() {
+        let mut strings = HashMap::new();
+        strings.insert("greeting".to_string(), StringEntry::Single("Hi {name}".to_string()));
+        let locale = test_locale(strings, HashMap::new());
+
+        let mut args = HashMap::new();
+        args.insert("name".to_string(), MessageArg::Str("Bob".to_string()));
+        assert_eq!(format(&locale, "greeting", &args), "Hi Bob");
+        assert_eq!(format(&locale, "missing_key", &args), "missing_key");
+    }
+}
@@ -0,0 +1,358 @@
+use std::collections::HashMap;
+
+/// The six CLDR plural categories. Not every locale uses every category;
+/// `other` always matches and is the catch-all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// Evaluation order mandated by CLDR: the first matching category wins.
+    pub const ORDER: [PluralCategory; 6] = [
+        PluralCategory::Zero,
+        PluralCategory::One,
+        PluralCategory::Two,
+        PluralCategory::Few,
+        PluralCategory::Many,
+        PluralCategory::Other,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+}
+
+/// The standard CLDR plural operands derived from a number.
+///
+/// `n` is the absolute value of the source number, `i` its integer part,
+/// `v`/`w` are the count of visible fraction digits with/without trailing
+/// zeros, and `f`/`t` are those fraction digits (with/without trailing
+/// zeros) read as an integer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PluralOperands {
+    pub n: f64,
+    pub i: u64,
+    pub v: u32,
+    pub f: u64,
+    pub t: u64,
+    pub w: u32,
+}
+
+impl PluralOperands {
+    /// Operands for a plain integer count: no visible fraction digits.
+    pub fn from_integer(n: u32) -> Self {
+        Self { n: n as f64, i: n as u64, v: 0, f: 0, t: 0, w: 0 }
+    }
+
+    /// Operands for a decimal count such as "1.50 hours". Fraction digits
+    /// are read off the number's natural decimal form, capped at three
+    /// places, which covers the currency and duration values this crate
+    /// formats.
+    pub fn from_decimal(value: f64) -> Self {
+        let n = value.abs();
+        let i = n.trunc() as u64;
+        let frac_str = format!("{:.3}", n - n.trunc());
+        let digits = frac_str.trim_start_matches("0.").trim_end_matches('0');
+
+        let v = digits.len() as u32;
+        let f: u64 = if digits.is_empty() { 0 } else { digits.parse().unwrap_or(0) };
+
+        // A bare `f64` carries no notion of written trailing zeros (100.0
+        // and 100.00 are the same value), so there's nothing distinct for
+        // `w`/`t` to trim off `v`/`f` here: both pairs track the same
+        // significant fraction digits.
+        Self { n, i, v, f, t: f, w: v }
+    }
+
+    fn value_of(&self, operand: char) -> Option<f64> {
+        match operand {
+            'n' => Some(self.n),
+            'i' => Some(self.i as f64),
+            'v' => Some(self.v as f64),
+            'f' => Some(self.f as f64),
+            't' => Some(self.t as f64),
+            'w' => Some(self.w as f64),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    Ident(String),
+    Num(i64),
+    Eq,
+    Ne,
+    Mod,
+    DotDot,
+    Comma,
+}
+
+fn tokenize(src: &str) -> Vec<Token> {
+    let chars: Vec<char> = src.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_alphanumeric() {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            tokens.push(Token::Num(text.parse().unwrap_or(0)));
+        } else if c == '.' && chars.get(i + 1) == Some(&'.') {
+            tokens.push(Token::DotDot);
+            i += 2;
+        } else if c == '!' && chars.get(i + 1) == Some(&'=') {
+            tokens.push(Token::Ne);
+            i += 2;
+        } else if c == '=' {
+            tokens.push(Token::Eq);
+            i += 1;
+        } else if c == '%' {
+            tokens.push(Token::Mod);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else {
+            i += 1;
+        }
+    }
+
+    tokens
+}
+
+#[derive(Debug, Clone)]
+struct Relation {
+    operand: char,
+    modulus: Option<i64>,
+    negated: bool,
+    ranges: Vec<(i64, i64)>,
+}
+
+impl Relation {
+    fn matches(&self, operands: &PluralOperands) -> bool {
+        let raw = operands.value_of(self.operand).unwrap_or(0.0);
+        let value = match self.modulus {
+            Some(m) if m != 0 => raw % (m as f64),
+            _ => raw,
+        };
+
+        let in_range = self.ranges.iter().any(|(lo, hi)| {
+            if lo == hi {
+                (value - *lo as f64).abs() < 1e-9
+            } else {
+                value.fract().abs() < 1e-9 && value >= *lo as f64 && value <= *hi as f64
+            }
+        });
+
+        in_range != self.negated
+    }
+}
+
+/// A parsed CLDR plural rule condition, e.g. `"i = 1 and v = 0"` or
+/// `"n % 10 = 3..4 and n % 100 != 13..14"`.
+#[derive(Debug, Clone)]
+pub struct PluralRule {
+    /// OR'd groups of AND'd relations.
+    or_groups: Vec<Vec<Relation>>,
+}
+
+impl PluralRule {
+    pub fn parse(src: &str) -> Self {
+        let tokens = tokenize(src);
+        let mut pos = 0;
+        let mut or_groups = Vec::new();
+        let mut and_group = Vec::new();
+
+        while pos < tokens.len() {
+            if let Some(relation) = Self::parse_relation(&tokens, &mut pos) {
+                and_group.push(relation);
+            }
+
+            match tokens.get(pos) {
+                Some(Token::Ident(word)) if word == "and" => {
+                    pos += 1;
+                }
+                Some(Token::Ident(word)) if word == "or" => {
+                    pos += 1;
+                    or_groups.push(std::mem::take(&mut and_group));
+                }
+                _ => break,
+            }
+        }
+
+        if !and_group.is_empty() {
+            or_groups.push(and_group);
+        }
+
+        Self { or_groups }
+    }
+
+    fn parse_relation(tokens: &[Token], pos: &mut usize) -> Option<Relation> {
+        let operand = match tokens.get(*pos)? {
+            Token::Ident(word) => word.chars().next()?,
+            _ => return None,
+        };
+        *pos += 1;
+
+        let modulus = if matches!(tokens.get(*pos), Some(Token::Mod)) {
+            *pos += 1;
+            match tokens.get(*pos) {
+                Some(Token::Num(n)) => {
+                    *pos += 1;
+                    Some(*n)
+                }
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        let negated = match tokens.get(*pos) {
+            Some(Token::Eq) => false,
+            Some(Token::Ne) => true,
+            _ => return None,
+        };
+        *pos += 1;
+
+        let mut ranges = Vec::new();
+        loop {
+            let lo = match tokens.get(*pos) {
+                Some(Token::Num(n)) => *n,
+                _ => break,
+            };
+            *pos += 1;
+
+            let hi = if matches!(tokens.get(*pos), Some(Token::DotDot)) {
+                *pos += 1;
+                match tokens.get(*pos) {
+                    Some(Token::Num(n)) => {
+                        *pos += 1;
+                        *n
+                    }
+                    _ => lo,
+                }
+            } else {
+                lo
+            };
+
+            ranges.push((lo, hi));
+
+            if matches!(tokens.get(*pos), Some(Token::Comma)) {
+                *pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        Some(Relation { operand, modulus, negated, ranges })
+    }
+
+    pub fn matches(&self, operands: &PluralOperands) -> bool {
+        self.or_groups.iter().any(|group| group.iter().all(|r| r.matches(operands)))
+    }
+}
+
+/// Parses the per-category condition table from `data_format.json`'s `PLURAL_RULES`
+/// (`{"one": "i = 1 and v = 0", "few": "..."}`) into ready-to-evaluate rules.
+pub fn parse_rules(raw: &HashMap<String, String>) -> HashMap<String, PluralRule> {
+    raw.iter().map(|(category, condition)| (category.clone(), PluralRule::parse(condition))).collect()
+}
+
+/// Selects the first matching category in CLDR evaluation order, falling
+/// back to `other` if nothing else matches (or is defined).
+pub fn select_category(rules: &HashMap<String, PluralRule>, operands: &PluralOperands) -> PluralCategory {
+    for category in PluralCategory::ORDER {
+        if category == PluralCategory::Other {
+            continue;
+        }
+        if let Some(rule) = rules.get(category.as_str()) {
+            if rule.matches(operands) {
+                return category;
+            }
+        }
+    }
+    PluralCategory::Other
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_decimal_tracks_significant_fraction_digits() {
+        let one_point_five = PluralOperands::from_decimal(1.5);
+        assert_eq!(one_point_five.v, 1);
+        assert_eq!(one_point_five.f, 5);
+        assert_eq!(one_point_five.w, 1);
+        assert_eq!(one_point_five.t, 5);
+    }
+
+    #[test]
+    fn from_decimal_of_a_whole_number_has_no_fraction_digits() {
+        for n in [2.0, 100.0] {
+            let operands = PluralOperands::from_decimal(n);
+            assert_eq!(operands.v, 0, "{n} should have v = 0");
+            assert_eq!(operands.f, 0, "{n} should have f = 0");
+        }
+    }
+
+    fn rules(pairs: &[(&str, &str)]) -> HashMap<String, PluralRule> {
+        let raw: HashMap<String, String> = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        parse_rules(&raw)
+    }
+
+    #[test]
+    fn polish_one_requires_integer_with_no_fraction() {
+        let rules = rules(&[("one", "i = 1 and v = 0")]);
+        assert_eq!(select_category(&rules, &PluralOperands::from_integer(1)), PluralCategory::One);
+        assert_eq!(select_category(&rules, &PluralOperands::from_decimal(1.0)), PluralCategory::One);
+        assert_eq!(select_category(&rules, &PluralOperands::from_decimal(1.5)), PluralCategory::Other);
+    }
+
+    #[test]
+    fn polish_few_uses_modulo_ranges_and_exclusion() {
+        let rules = rules(&[("few", "v = 0 and i % 10 = 2..4 and i % 100 != 12..14")]);
+        assert_eq!(select_category(&rules, &PluralOperands::from_integer(2)), PluralCategory::Few);
+        assert_eq!(select_category(&rules, &PluralOperands::from_integer(22)), PluralCategory::Few);
+        assert_eq!(select_category(&rules, &PluralOperands::from_integer(12)), PluralCategory::Other);
+        assert_eq!(select_category(&rules, &PluralOperands::from_integer(5)), PluralCategory::Other);
+    }
+
+    #[test]
+    fn first_matching_category_in_cldr_order_wins() {
+        let rules = rules(&[("one", "n = 1"), ("other", "n = 1")]);
+        assert_eq!(select_category(&rules, &PluralOperands::from_integer(1)), PluralCategory::One);
+    }
+
+    #[test]
+    fn undefined_rules_fall_back_to_other() {
+        let rules = rules(&[]);
+        assert_eq!(select_category(&rules, &PluralOperands::from_integer(7)), PluralCategory::Other);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::data_format::SpelloutRule;
+
+enum Part<'a> {
+    Literal(&'a str),
+    Quotient,
+    Remainder,
+}
+
+/// Splits a rule's text on its `<<` (quotient) and `>>` (remainder)
+/// substitution markers, preserving the literal text between them.
+fn split_markers(text: &str) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        let next_quotient = rest.find("<<");
+        let next_remainder = rest.find(">>");
+
+        let (idx, marker) = match (next_quotient, next_remainder) {
+            (None, None) => {
+                parts.push(Part::Literal(rest));
+                break;
+            }
+            (Some(q), None) => (q, Part::Quotient),
+            (None, Some(r)) => (r, Part::Remainder),
+            (Some(q), Some(r)) if q < r => (q, Part::Quotient),
+            (Some(_), Some(r)) => (r, Part::Remainder),
+        };
+
+        if idx > 0 {
+            parts.push(Part::Literal(&rest[..idx]));
+        }
+        parts.push(marker);
+        rest = &rest[idx + 2..];
+    }
+
+    parts
+}
+
+/// Spells out `n` using the largest rule whose base does not exceed it,
+/// recursively rendering the quotient and remainder of `n / rule.base`
+/// wherever the rule's text marks `<<`/`>>`.
+pub fn spellout(ruleset: &[SpelloutRule], n: i64) -> String {
+    if n < 0 {
+        return format!("negative {}", spellout(ruleset, -n));
+    }
+
+    let Some(rule) = ruleset.iter().filter(|r| r.base <= n).max_by_key(|r| r.base) else {
+        return n.to_string();
+    };
+
+    let base = rule.base.max(1);
+    let quotient = n / base;
+    let remainder = n % base;
+
+    let mut out = String::new();
+    for part in split_markers(&rule.text) {
+        match part {
+            Part::Literal(s) => out.push_str(s),
+            // `quotient != n`/`remainder != n` guard against a rule whose
+            // base can't make progress (a base of 0 or 1, whose quotient is
+            // n itself) recursing forever.
+            Part::Quotient if quotient > 0 && quotient != n => out.push_str(&spellout(ruleset, quotient)),
+            Part::Remainder if remainder > 0 && remainder != n => out.push_str(&spellout(ruleset, remainder)),
+            _ => {}
+        }
+    }
+
+    out.trim().to_string()
+}
+
+/// Spells out a monetary amount as "<integer> <major unit> [and <fraction>
+/// <minor unit>]", e.g. "one hundred dollars and fifty cents".
+pub fn spellout_currency(ruleset: &[SpelloutRule], amount: f64, major_unit: &str, minor_unit: &str) -> String {
+    let rounded = (amount.abs() * 100.0).round() / 100.0;
+    let integer_part = rounded.trunc() as i64;
+    let fractional_part = ((rounded - rounded.trunc()) * 100.0).round() as i64;
+
+    let mut out = format!("{} {}", spellout(ruleset, integer_part), major_unit);
+    if fractional_part > 0 {
+        out.push_str(&format!(" and {} {}", spellout(ruleset, fractional_part), minor_unit));
+    }
+
+    if amount.is_sign_negative() {
+        out = format!("negative {}", out);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn multilevel_ruleset() -> Vec<SpelloutRule> {
+        vec![
+            SpelloutRule { base: 0, text: "zero".into() },
+            SpelloutRule { base: 1, text: "one".into() },
+            SpelloutRule { base: 2, text: "two".into() },
+            SpelloutRule { base: 3, text: "three".into() },
+            SpelloutRule { base: 4, text: "four".into() },
+            SpelloutRule { base: 5, text: "five".into() },
+            SpelloutRule { base: 10, text: "<< ten >>".into() },
+            SpelloutRule { base: 100, text: "<< hundred >>".into() },
+            SpelloutRule { base: 1000, text: "<< thousand >>".into() },
+        ]
+    }
+
+    #[test]
+    fn recurses_through_thousands_hundreds_and_tens() {
+        let ruleset = multilevel_ruleset();
+        assert_eq!(spellout(&ruleset, 234), "two hundred three ten four");
+        assert_eq!(spellout(&ruleset, 2345), "two thousand three hundred four ten five");
+    }
+
+    #[test]
+    fn negative_numbers_get_a_negative_prefix() {
+        let ruleset = multilevel_ruleset();
+        assert_eq!(spellout(&ruleset, -5), "negative five");
+    }
+
+    #[test]
+    fn a_rule_without_a_remainder_marker_drops_the_remainder() {
+        let ruleset = vec![
+            SpelloutRule { base: 0, text: "zero".into() },
+            SpelloutRule { base: 1, text: "one".into() },
+            SpelloutRule { base: 5, text: "five".into() },
+            SpelloutRule { base: 20, text: "<< twenty".into() },
+        ];
+        // 25 = 1*20 + 5, but the base-20 rule has no `>>` marker, so the
+        // remainder (5, which would spell "five") is never appended.
+        assert_eq!(spellout(&ruleset, 25), "one twenty");
+    }
+
+    #[test]
+    fn spellout_currency_combines_major_and_minor_units() {
+        let ruleset = vec![
+            SpelloutRule { base: 0, text: "zero".into() },
+            SpelloutRule { base: 1, text: "one".into() },
+            SpelloutRule { base: 2, text: "two".into() },
+            SpelloutRule { base: 5, text: "five".into() },
+            SpelloutRule { base: 50, text: "fifty".into() },
+        ];
+
+        assert_eq!(spellout_currency(&ruleset, 2.50, "dollars", "cents"), "two dollars and fifty cents");
+        assert_eq!(spellout_currency(&ruleset, 1.0, "dollar", "cent"), "one dollar");
+        assert_eq!(spellout_currency(&ruleset, -5.0, "dollars", "cents"), "negative five dollars");
+    }
+}
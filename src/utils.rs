@@ -15,6 +15,7 @@ fn is_root() -> bool {
 // unix
 #[cfg(unix)]
 use nix::unistd::Uid;
+use crate::error::Result;
 use crate::Settings;
 
 #[cfg(unix)]
@@ -23,15 +24,17 @@ fn is_root() -> bool {
 }
 
 // config or paths
-pub fn ensure_that_config_exists(settings_file_path: PathBuf) {
+pub fn ensure_that_config_exists(settings_file_path: PathBuf) -> Result<()> {
     if !settings_file_path.exists() {
         let default_settings = Settings {
             default_locale: "en_US".into(),
             fallback_locale: "en_US".into(),
+            fallback_chain: None,
         };
-        let file = File::create(&settings_file_path).unwrap();
-        serde_json::to_writer(file, &default_settings).unwrap();
+        let file = File::create(&settings_file_path)?;
+        serde_json::to_writer(file, &default_settings)?;
     }
+    Ok(())
 }
 fn can_write_dir(path: &Path) -> bool {
     if !path.exists() {